@@ -30,6 +30,61 @@ Lita.configure do |config|
   config.redis[:user] = ENV['DIABOT_REDIS_USER']
   config.redis[:password] = ENV['DIABOT_REDIS_PASS']
 
+  # Report handler errors to Sentry. Leave SENTRY_DSN unset to disable.
+  # Only exceptions raised inside Lita handlers are captured; adapter and
+  # startup errors are not. Lita's error metadata doesn't name the handler
+  # or route (the backtrace does), and the Discord source carries no guild,
+  # so events are tagged with the room only. Breadcrumbs aren't recorded.
+  #
+  # Chat text and user identity stay out of Sentry unless
+  # SENTRY_SEND_MESSAGES is set, since messages here often hold health data.
+  if ENV['SENTRY_DSN']
+    require 'raven'
+
+    # One worker sends events; when it falls behind, new events are dropped
+    # instead of blocking handlers or piling up threads.
+    sentry_queue = SizedQueue.new(100)
+    Thread.new do
+      loop do
+        begin
+          Raven.send_event(sentry_queue.pop)
+        rescue StandardError => error
+          Lita.logger.warn("Failed to send error to Sentry: #{error.message}")
+        end
+      end
+    end
+
+    Raven.configure do |raven|
+      raven.dsn = ENV['SENTRY_DSN']
+      raven.async = lambda do |event|
+        begin
+          sentry_queue.push(event, true)
+        rescue ThreadError
+          Lita.logger.warn('Sentry queue is full, dropping error report')
+        end
+      end
+    end
+
+    config.robot.error_handler = lambda do |error, metadata|
+      context = { tags: {}, extra: {} }
+
+      if (message = metadata[:message])
+        context[:tags][:room] = message.source.room if message.source.room
+        if ENV['SENTRY_SEND_MESSAGES']
+          context[:user] = { id: message.user.id, username: message.user.name }
+          context[:extra][:command] = message.body
+        end
+      end
+
+      # Never send the whole Rack env, it carries auth headers and cookies.
+      if (env = metadata[:env])
+        context[:extra][:request] = "#{env['REQUEST_METHOD']} #{env['PATH_INFO']}"
+      end
+
+      Raven.capture_exception(error, context)
+    end
+  end
+
   ## Example: Set configuration for any loaded handlers. See the handler's
   ## documentation for options.
   # config.handlers.some_handler.some_config_key = "value"